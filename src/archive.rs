@@ -0,0 +1,242 @@
+//! # Archive
+//!
+//! This module descends into container formats (ZIP, TAR, and gzip-compressed
+//! TAR) and verifies each embedded entry with the same extension-vs-content
+//! check applied to regular files. Entries are reported with a composite path
+//! such as `photos.zip!/avatar.png`.
+//!
+//! To stay memory-bounded, only the leading `trie.max_buffer_size` bytes of
+//! each entry are read, and the number of entries inspected is capped to guard
+//! against decompression bombs.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+
+use crate::trie::MagicNumberTrie;
+use crate::FileResult;
+
+/// Offset of the `ustar` magic within a POSIX tar header.
+const TAR_MAGIC_OFFSET: usize = 257;
+/// The `ustar` magic marking a POSIX tar header.
+const TAR_MAGIC: &[u8] = b"ustar";
+
+/// A container format Sigil can descend into.
+#[derive(Clone, Copy, Debug)]
+pub enum ArchiveKind {
+    Zip,
+    Tar,
+    Gzip,
+}
+
+impl ArchiveKind {
+    /// Maps a detected file type to the container format it represents, if any.
+    pub fn from_type(file_type: &str) -> Option<Self> {
+        match file_type.to_uppercase().as_str() {
+            "ZIP" => Some(ArchiveKind::Zip),
+            "TAR" => Some(ArchiveKind::Tar),
+            "GZIP" | "GZ" => Some(ArchiveKind::Gzip),
+            _ => None,
+        }
+    }
+}
+
+/// Opens the container at `path` and verifies each of its entries.
+///
+/// Gzip streams are transparently decompressed; one that wraps a TAR (the
+/// common `.tar.gz` case) is descended into, and a standalone gzip is verified
+/// as a single member. At most `max_entries` entries are inspected;
+/// if the archive holds more, the overflow is reported as an error.
+pub fn verify_archive(
+    path: &Path,
+    kind: ArchiveKind,
+    trie: &MagicNumberTrie,
+    max_entries: usize,
+) -> Vec<FileResult> {
+    match kind {
+        ArchiveKind::Zip => verify_zip(path, trie, max_entries),
+        ArchiveKind::Tar => verify_tar(path, trie, max_entries),
+        ArchiveKind::Gzip => verify_gzip(path, trie, max_entries),
+    }
+}
+
+/// Verifies the members of a ZIP archive.
+fn verify_zip(path: &Path, trie: &MagicNumberTrie, max_entries: usize) -> Vec<FileResult> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return vec![open_error(path, e)],
+    };
+    let mut archive = match zip::ZipArchive::new(file) {
+        Ok(archive) => archive,
+        Err(e) => return vec![FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() }],
+    };
+
+    let mut results = Vec::new();
+    let total = archive.len();
+    let limit = total.min(max_entries);
+    if total > max_entries {
+        results.push(bomb_error(path, max_entries));
+    }
+
+    for i in 0..limit {
+        let mut entry = match archive.by_index(i) {
+            Ok(entry) => entry,
+            Err(e) => {
+                results.push(FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() });
+                continue;
+            }
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let name = entry.name().to_string();
+        let buffer = read_capped(&mut entry, trie.max_buffer_size);
+        results.push(verify_entry(composite_path(path, &name), &name, &buffer, trie));
+    }
+
+    results
+}
+
+/// Verifies the members of a TAR archive.
+fn verify_tar(path: &Path, trie: &MagicNumberTrie, max_entries: usize) -> Vec<FileResult> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return vec![open_error(path, e)],
+    };
+    verify_tar_reader(path, file, trie, max_entries)
+}
+
+/// Verifies a gzip stream, descending into it as a TAR when it wraps one.
+///
+/// The decompressed header is peeked to tell a `.tar.gz` apart from a standalone
+/// gzip (e.g. `notes.txt.gz`). A bare gzip is treated as a single member whose
+/// declared name is the archive name with the `.gz` suffix stripped.
+fn verify_gzip(path: &Path, trie: &MagicNumberTrie, max_entries: usize) -> Vec<FileResult> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => return vec![open_error(path, e)],
+    };
+
+    let peek_len = (trie.max_buffer_size as usize).max(TAR_MAGIC_OFFSET + TAR_MAGIC.len());
+    let mut header = Vec::new();
+    if let Err(e) = GzDecoder::new(file).take(peek_len as u64).read_to_end(&mut header) {
+        return vec![FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() }];
+    }
+
+    if is_tar_header(&header) {
+        // A genuine tar.gz: re-open and stream the members.
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => return vec![open_error(path, e)],
+        };
+        return verify_tar_reader(path, GzDecoder::new(file), trie, max_entries);
+    }
+
+    // A standalone gzip: verify its single decompressed member.
+    let name = gzip_member_name(path);
+    header.truncate(trie.max_buffer_size as usize);
+    vec![verify_entry(composite_path(path, &name), &name, &header, trie)]
+}
+
+/// Verifies the members of a TAR archive read from an arbitrary reader.
+fn verify_tar_reader<R: Read>(path: &Path, reader: R, trie: &MagicNumberTrie, max_entries: usize) -> Vec<FileResult> {
+    let mut archive = tar::Archive::new(reader);
+
+    let entries = match archive.entries() {
+        Ok(entries) => entries,
+        Err(e) => return vec![FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() }],
+    };
+
+    let mut results = Vec::new();
+    for (i, entry) in entries.enumerate() {
+        if i >= max_entries {
+            results.push(bomb_error(path, max_entries));
+            break;
+        }
+
+        let mut entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                results.push(FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() });
+                continue;
+            }
+        };
+
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let name = match entry.path() {
+            Ok(name) => name.display().to_string(),
+            Err(e) => {
+                results.push(FileResult::Error { path: path.to_path_buf(), error_message: e.to_string() });
+                continue;
+            }
+        };
+        let buffer = read_capped(&mut entry, trie.max_buffer_size);
+        results.push(verify_entry(composite_path(path, &name), &name, &buffer, trie));
+    }
+
+    results
+}
+
+/// Runs the extension-vs-content check on a single archive entry.
+fn verify_entry(composite: PathBuf, name: &str, buffer: &[u8], trie: &MagicNumberTrie) -> FileResult {
+    let declared_type = match Path::new(name).extension().and_then(|e| e.to_str()) {
+        Some(extension) => extension.to_uppercase(),
+        None => {
+            return FileResult::Error {
+                path: composite,
+                error_message: "File has no extension".to_string(),
+            };
+        }
+    };
+
+    match trie.search(buffer) {
+        Some(actual_type) if trie.accepts(&actual_type, &declared_type) => FileResult::Correct { path: composite },
+        Some(actual_type) => FileResult::Incorrect { path: composite, declared_type, actual_type },
+        None => FileResult::Incorrect { path: composite, declared_type, actual_type: "Unknown".to_string() },
+    }
+}
+
+/// Reads at most `max_buffer_size` bytes from an archive member.
+fn read_capped<R: Read>(reader: &mut R, max_buffer_size: u32) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let _ = reader.take(max_buffer_size as u64).read_to_end(&mut buffer);
+    buffer
+}
+
+/// Returns true if `header` carries the POSIX tar `ustar` magic.
+fn is_tar_header(header: &[u8]) -> bool {
+    header.len() >= TAR_MAGIC_OFFSET + TAR_MAGIC.len()
+        && &header[TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len()] == TAR_MAGIC
+}
+
+/// Derives the declared name of a standalone gzip's member by stripping the
+/// `.gz` suffix from the archive name.
+fn gzip_member_name(path: &Path) -> String {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    name.strip_suffix(".gz")
+        .or_else(|| name.strip_suffix(".GZ"))
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// Builds the composite path used to report an archive member.
+fn composite_path(archive: &Path, entry_name: &str) -> PathBuf {
+    PathBuf::from(format!("{}!/{}", archive.display(), entry_name))
+}
+
+/// Builds the error reported when an archive cannot be opened.
+fn open_error(path: &Path, error: std::io::Error) -> FileResult {
+    FileResult::Error { path: path.to_path_buf(), error_message: error.to_string() }
+}
+
+/// Builds the error reported when an archive exceeds the entry cap.
+fn bomb_error(path: &Path, max_entries: usize) -> FileResult {
+    FileResult::Error {
+        path: path.to_path_buf(),
+        error_message: format!("Archive has more than {} entries; skipping the rest", max_entries),
+    }
+}
@@ -6,14 +6,27 @@
 
 mod trie;
 mod file_manager;
+mod progress;
+mod archive;
 
 use crate::trie::MagicNumberTrie;
 use crate::file_manager::{FileSignature, get_file_info};
+use crate::progress::{ProgressData, spawn_reporter};
+use crate::archive::{ArchiveKind, verify_archive};
+use std::collections::HashSet;
 use std::fs::{self};
-use std::path::{PathBuf};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use colored::*;
+use crossbeam_channel::unbounded;
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use walkdir::WalkDir;
+use serde::Serialize;
+
+/// The maximum number of symlink hops followed before a path is treated as a
+/// cycle and reported as an error.
+const MAX_SYMLINK_HOPS: usize = 20;
 
 /// Configuration for the Sigil application.
 ///
@@ -26,10 +39,101 @@ pub struct AppConfig {
     pub input_json_file: Option<PathBuf>,
     /// Flag to activate recursive directory traversal.
     pub recursive: bool,
+    /// Rename files whose extension contradicts their detected type.
+    pub fix: bool,
+    /// Print the renames that `fix` would perform without touching disk.
+    pub dry_run: bool,
+    /// Glob patterns; only matching files are verified (empty means all).
+    pub include: Vec<String>,
+    /// Glob patterns; matching files and directories are skipped.
+    pub exclude: Vec<String>,
+    /// The format used to render the report.
+    pub format: OutputFormat,
+    /// Descend into container formats (ZIP/TAR/GZIP) and verify their entries.
+    pub into_archives: bool,
+    /// The maximum number of entries inspected per archive (bomb guard).
+    pub max_archive_entries: usize,
+}
+
+/// Compiled include/exclude glob matchers applied during traversal.
+///
+/// A file is kept when it is not matched by any exclude pattern and, if any
+/// include patterns were supplied, is matched by at least one of them.
+/// Directories matching an exclude pattern are pruned wholesale.
+struct PathFilter {
+    include: GlobSet,
+    exclude: GlobSet,
+}
+
+impl PathFilter {
+    /// Compiles the include and exclude patterns into matchers.
+    fn build(include: &[String], exclude: &[String]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(PathFilter {
+            include: build_globset(include)?,
+            exclude: build_globset(exclude)?,
+        })
+    }
+
+    /// Returns true if `path` is pruned by an exclude pattern.
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude.is_match(path)
+    }
+
+    /// Returns true if `path` should be verified.
+    fn accepts_file(&self, path: &Path) -> bool {
+        if self.exclude.is_match(path) {
+            return false;
+        }
+        self.include.is_empty() || self.include.is_match(path)
+    }
+}
+
+/// Builds a `GlobSet` from a list of patterns.
+fn build_globset(patterns: &[String]) -> Result<GlobSet, Box<dyn std::error::Error + Send + Sync>> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        for expanded in expand_pattern(pattern) {
+            builder.add(Glob::new(&expanded)?);
+        }
+    }
+    Ok(builder.build()?)
+}
+
+/// Expands a user-supplied glob into the patterns actually matched.
+///
+/// Matching runs against the full relative path, so a trailing `/` (the
+/// directory form like `node_modules/`) is dropped and any pattern without a
+/// path separator is additionally matched at every depth via a `**/` prefix.
+/// This makes `--include '*.jpg'` and `--exclude node_modules/` behave as a
+/// user would expect regardless of how deep the match lies.
+fn expand_pattern(pattern: &str) -> Vec<String> {
+    let trimmed = pattern.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+
+    let mut expanded = vec![trimmed.to_string()];
+    if !trimmed.contains('/') {
+        expanded.push(format!("**/{}", trimmed));
+    }
+    expanded
 }
 
+/// The format used to render the final verification report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text with colors and emojis (on a TTY).
+    Text,
+    /// A single JSON object with a summary and per-file results.
+    Json,
+    /// One CSV row per file.
+    Csv,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
 pub enum FileResult {
-    Correct(PathBuf),
+    Correct { path: PathBuf },
     Incorrect {
         path: PathBuf,
         declared_type: String,
@@ -39,6 +143,10 @@ pub enum FileResult {
         path: PathBuf,
         error_message: String,
     },
+    Fixed {
+        path: PathBuf,
+        new_path: PathBuf,
+    },
 }
 
 /// Runs the main logic of the Sigil application.
@@ -62,69 +170,190 @@ pub enum FileResult {
 pub fn run(config: AppConfig) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let trie = match config.input_json_file {
         Some(path) => {
-            println!("Trie initialized successfully from '{}'.", path.display());
+            eprintln!("Trie initialized successfully from '{}'.", path.display());
             MagicNumberTrie::from_file(&path)?
         }
         None => {
-            println!("Trie initialized successfully from embedded JSON.");
+            eprintln!("Trie initialized successfully from embedded JSON.");
             let json_data = include_str!("../data/magic_numbers_reference.json");
             MagicNumberTrie::from_str(json_data)?
         }
     };
-    println!("Max buffer size: {} bytes.", trie.max_buffer_size);
+    eprintln!("Max buffer size: {} bytes.", trie.max_buffer_size);
 
     let path = config.path;
+    let fix = config.fix;
+    let dry_run = config.dry_run;
+    let format = config.format;
+    let into_archives = config.into_archives;
+    let max_archive_entries = config.max_archive_entries;
     let mut results: Vec<FileResult> = Vec::new();
 
-    println!("\nStarting verification...");
+    eprintln!("\nStarting verification...");
 
     if path.is_dir() {
-        let path_list = resolve_path(&path, config.recursive)?;
+        let filter = PathFilter::build(&config.include, &config.exclude)?;
+
+        let (sender, receiver) = unbounded::<ProgressData>();
+        let entries_checked = Arc::new(AtomicUsize::new(0));
+        let reporter = spawn_reporter(receiver, Arc::clone(&entries_checked));
 
-        results = path_list.into_par_iter().map(|file_path| process_file(file_path, &trie)).collect();
+        // Stage 1: path discovery.
+        let _ = sender.send(ProgressData { current_stage: 1, max_stage: 2, entries_checked: 0, entries_to_check: 0 });
+        let (path_list, mut walk_errors) = resolve_path(&path, config.recursive, &filter)?;
+
+        // Stage 2: verification. The total is known now that discovery is done.
+        let entries_to_check = path_list.len();
+        let _ = sender.send(ProgressData { current_stage: 2, max_stage: 2, entries_checked: 0, entries_to_check });
+
+        results = path_list
+            .into_par_iter()
+            .flat_map(|file_path| {
+                let file_results = process_file(file_path, &trie, fix, dry_run, into_archives, max_archive_entries);
+                entries_checked.fetch_add(1, Ordering::Relaxed);
+                file_results
+            })
+            .collect();
+
+        // Closing the channel tells the reporter to exit.
+        drop(sender);
+        let _ = reporter.join();
+
+        // Surface traversal problems (cycles, broken symlinks) alongside the results.
+        results.append(&mut walk_errors);
     } else {
-        results.push(process_file(path, &trie));
+        results.extend(process_file(path, &trie, fix, dry_run, into_archives, max_archive_entries));
     }
 
-    report(results);
-    
+    report(results, format);
+
     Ok(())
 }
 
 /// Discovers files to be processed based on the given path and recursive flag.
 ///
-/// If `recursive_flag` is false, it returns a list of files directly within `folder_path`.
-/// If `recursive_flag` is true, it performs a recursive search for all files within `folder_path`.
+/// If `recursive_flag` is false, only files directly within `folder_path` are
+/// returned. If `recursive_flag` is true, the directory is walked depth-first.
+/// The walk is protected against symlink loops (canonicalized directories are
+/// tracked and the number of followed symlink hops is capped) and honors the
+/// include/exclude patterns in `filter`.
+///
+/// Cycles and broken symlinks do not abort the walk; they are collected as
+/// `FileResult::Error` values so the caller can report them alongside the
+/// verification results.
 ///
 /// # Arguments
 ///
 /// * `folder_path` - The directory to search for files.
 /// * `recursive_flag` - A boolean to control recursive search.
+/// * `filter` - The compiled include/exclude matchers applied during traversal.
 ///
 /// # Errors
 ///
-/// Returns an error if the directory cannot be read.
-fn resolve_path(folder_path: &PathBuf, recursive_flag: bool) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+/// This function does not return `Err`; per-entry problems are returned in the
+/// `FileResult` vector.
+fn resolve_path(
+    folder_path: &Path,
+    recursive_flag: bool,
+    filter: &PathFilter,
+) -> Result<(Vec<PathBuf>, Vec<FileResult>), Box<dyn std::error::Error + Send + Sync>> {
     let mut files_path = Vec::new();
+    let mut errors = Vec::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+
+    if let Ok(canonical) = fs::canonicalize(folder_path) {
+        visited.insert(canonical);
+    }
 
-    if !recursive_flag {
-        for entry in fs::read_dir(folder_path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
-                files_path.push(path);
+    collect_files(folder_path, recursive_flag, 0, filter, &mut visited, &mut files_path, &mut errors);
+
+    Ok((files_path, errors))
+}
+
+/// Walks a single directory, recursing when `recursive_flag` is set.
+///
+/// `hops` counts how many symlinks have been followed on the path to `dir`;
+/// exceeding `MAX_SYMLINK_HOPS` stops descent. `visited` holds the canonicalized
+/// directories already seen so cycles can be detected.
+fn collect_files(
+    dir: &Path,
+    recursive_flag: bool,
+    hops: usize,
+    filter: &PathFilter,
+    visited: &mut HashSet<PathBuf>,
+    files_path: &mut Vec<PathBuf>,
+    errors: &mut Vec<FileResult>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            errors.push(FileResult::Error { path: dir.to_path_buf(), error_message: e.to_string() });
+            return;
+        }
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors.push(FileResult::Error { path: dir.to_path_buf(), error_message: e.to_string() });
+                continue;
             }
+        };
+        let path = entry.path();
+
+        // Directories and files matching an exclude pattern are pruned wholesale.
+        if filter.is_excluded(&path) {
+            continue;
         }
-    } else {
-        files_path = WalkDir::new(folder_path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .map(|e| e.path().to_path_buf())
-            .collect();
-    }
 
-    Ok(files_path)
+        let is_symlink = entry.file_type().map(|t| t.is_symlink()).unwrap_or(false);
+        let entry_hops = if is_symlink { hops + 1 } else { hops };
+        if entry_hops > MAX_SYMLINK_HOPS {
+            errors.push(FileResult::Error {
+                path,
+                error_message: format!("Exceeded maximum of {} symlink hops", MAX_SYMLINK_HOPS),
+            });
+            continue;
+        }
+
+        // `metadata` follows symlinks; a failure here means a broken link.
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                errors.push(FileResult::Error {
+                    path,
+                    error_message: format!("Broken symlink or unreadable entry: {}", e),
+                });
+                continue;
+            }
+        };
+
+        if metadata.is_dir() {
+            if !recursive_flag {
+                continue;
+            }
+
+            let canonical = match fs::canonicalize(&path) {
+                Ok(canonical) => canonical,
+                Err(e) => {
+                    errors.push(FileResult::Error { path, error_message: e.to_string() });
+                    continue;
+                }
+            };
+            if !visited.insert(canonical) {
+                errors.push(FileResult::Error {
+                    path,
+                    error_message: "Symlink cycle detected".to_string(),
+                });
+                continue;
+            }
+
+            collect_files(&path, recursive_flag, entry_hops, filter, visited, files_path, errors);
+        } else if metadata.is_file() && filter.accepts_file(&path) {
+            files_path.push(path);
+        }
+    }
 }
 
 /// Processes a single file to verify its type and returns a `FileResult`.
@@ -137,31 +366,167 @@ fn resolve_path(folder_path: &PathBuf, recursive_flag: bool) -> Result<Vec<PathB
 ///
 /// * `path` - The path to the file to be processed.
 /// * `trie` - A reference to the `MagicNumberTrie` containing known file signatures.
-fn process_file(path: PathBuf, trie: &MagicNumberTrie) -> FileResult {
+/// * `fix` - When true, rename files whose extension contradicts their detected type.
+/// * `dry_run` - When true, report the rename that `fix` would perform without touching disk.
+/// * `into_archives` - When true, descend into container formats and verify their entries.
+/// * `max_archive_entries` - The per-archive entry cap used when descending.
+///
+/// The result is a vector because a single container can expand into one result
+/// for the archive itself plus one per embedded entry.
+fn process_file(
+    path: PathBuf,
+    trie: &MagicNumberTrie,
+    fix: bool,
+    dry_run: bool,
+    into_archives: bool,
+    max_archive_entries: usize,
+) -> Vec<FileResult> {
     if !path.is_file() {
-        return FileResult::Error {
+        return vec![FileResult::Error {
             path,
             error_message: "The provided path is not a file.".to_string(),
-        };
+        }];
     }
 
     let mut file_info: FileSignature = match get_file_info(path.clone(), trie.max_buffer_size) {
         Ok(info) => info,
         Err(e) => {
-            return FileResult::Error { path, error_message: e.to_string() };
+            return vec![FileResult::Error { path, error_message: e.to_string() }];
         }
     };
 
-    if let Some(actual_type) = trie.search(&file_info.buffer) {
-        file_info.actual_type = actual_type;
+    let detected = trie.search(&file_info.buffer);
+
+    let outer = match detected.clone() {
+        Some(actual_type) => {
+            file_info.actual_type = actual_type;
 
-        if file_info.actual_type.contains(&file_info.declared_type) {
-            FileResult::Correct(path)
-        } else {
-            FileResult::Incorrect { path, declared_type: file_info.declared_type, actual_type: file_info.actual_type }
+            if trie.accepts(&file_info.actual_type, &file_info.declared_type) {
+                FileResult::Correct { path: path.clone() }
+            } else if fix || dry_run {
+                match trie.extensions.get(&file_info.actual_type) {
+                    Some(extension) => apply_fix(path.clone(), &file_info, extension, dry_run),
+                    None => FileResult::Incorrect { path: path.clone(), declared_type: file_info.declared_type.clone(), actual_type: file_info.actual_type.clone() },
+                }
+            } else {
+                FileResult::Incorrect { path: path.clone(), declared_type: file_info.declared_type.clone(), actual_type: file_info.actual_type.clone() }
+            }
         }
+        None => FileResult::Incorrect { path: path.clone(), declared_type: file_info.declared_type.clone(), actual_type: "Unknown".to_string() },
+    };
+
+    // Do not descend into a container that was fixed: a `--fix` rename has
+    // moved it out from under us, and a `--dry-run` plan skips it the same way.
+    let was_fixed = matches!(outer, FileResult::Fixed { .. });
+    let mut results = vec![outer];
+    if into_archives && !was_fixed {
+        if let Some(kind) = detected.as_deref().and_then(ArchiveKind::from_type) {
+            results.extend(verify_archive(&path, kind, trie, max_archive_entries));
+        }
+    }
+
+    results
+}
+
+/// Repairs a file whose extension contradicts its detected type.
+///
+/// The file's extension (or a known compound extension, see [`file_base`]) is
+/// stripped and replaced with the one preferred by the detected type, so
+/// `archive.tar.gz` detected as PNG becomes `archive.png` rather than
+/// `archive.tar.png`, while `report.v2.pdf` becomes `report.v2.png`. When the target name is already
+/// taken, a numeric component is inserted before the extension (`name.1.png`).
+/// The free target is reserved atomically with `create_new`, which makes the
+/// rename safe under the parallel scan: two files that would resolve to the same
+/// name can no longer clobber each other. When `dry_run` is set the rename is
+/// only predicted, not performed.
+fn apply_fix(path: PathBuf, file_info: &FileSignature, extension: &str, dry_run: bool) -> FileResult {
+    let base = file_base(&path);
+
+    if dry_run {
+        let new_path = predicted_path(&path, &base, extension);
+        return FileResult::Fixed { path, new_path };
+    }
+
+    match reserve_and_rename(&path, &base, extension) {
+        Ok(new_path) => FileResult::Fixed { path, new_path },
+        Err(e) => FileResult::Error {
+            path,
+            error_message: format!(
+                "Failed to fix '{}' extension: {}",
+                file_info.declared_type, e
+            ),
+        },
+    }
+}
+
+/// Known multi-part extensions stripped whole during repair.
+const COMPOUND_EXTENSIONS: &[&str] = &[".tar.gz", ".tar.bz2", ".tar.xz", ".tar.zst"];
+
+/// Returns the file name with its extension removed, preserving interior dots.
+///
+/// Only the trailing extension is stripped (`report.v2.pdf` -> `report.v2`),
+/// except for the known compound extensions in [`COMPOUND_EXTENSIONS`], which
+/// are stripped whole (`archive.tar.gz` -> `archive`). Hidden files with no
+/// extension are kept intact (`.bashrc` -> `.bashrc`).
+fn file_base(path: &Path) -> String {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+
+    let lower = name.to_lowercase();
+    for compound in COMPOUND_EXTENSIONS {
+        if lower.ends_with(compound) {
+            return name[..name.len() - compound.len()].to_string();
+        }
+    }
+
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or(name).to_string()
+}
+
+/// Builds the repair target for `base`/`extension`; a non-zero `counter` is
+/// inserted before the extension (`name.1.png`). Whether the name is taken is
+/// not checked here.
+fn candidate_path(src: &Path, base: &str, extension: &str, counter: usize) -> PathBuf {
+    if counter == 0 {
+        src.with_file_name(format!("{}.{}", base, extension))
     } else {
-        FileResult::Incorrect { path, declared_type: file_info.declared_type, actual_type: "Unknown".to_string() }
+        src.with_file_name(format!("{}.{}.{}", base, counter, extension))
+    }
+}
+
+/// Atomically reserves a free target name and renames `src` onto it.
+///
+/// Each candidate is reserved with `create_new`; a concurrent fixer that loses
+/// the race observes `AlreadyExists` and moves on to the next numbered name, so
+/// no two renames can target the same path.
+fn reserve_and_rename(src: &Path, base: &str, extension: &str) -> std::io::Result<PathBuf> {
+    let mut counter = 0;
+    loop {
+        let candidate = candidate_path(src, base, extension, counter);
+        if candidate == src {
+            return Ok(candidate);
+        }
+
+        match fs::OpenOptions::new().write(true).create_new(true).open(&candidate) {
+            Ok(_) => {
+                fs::rename(src, &candidate)?;
+                return Ok(candidate);
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::AlreadyExists => counter += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Predicts the target `apply_fix` would choose, without touching disk.
+///
+/// Used only for `--dry-run`; the `exists` probe is inherently best-effort.
+fn predicted_path(src: &Path, base: &str, extension: &str) -> PathBuf {
+    let mut counter = 0;
+    loop {
+        let candidate = candidate_path(src, base, extension, counter);
+        if candidate == src || !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
     }
 }
 
@@ -178,17 +543,31 @@ fn process_file(path: PathBuf, trie: &MagicNumberTrie) -> FileResult {
 /// # Arguments
 ///
 /// * `results` - A vector of `FileResult` containing the outcome for each processed file.
-fn report(results: Vec<FileResult>) {
+/// * `format` - The output format to render.
+fn report(results: Vec<FileResult>, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => report_text(results),
+        OutputFormat::Json => report_json(&results),
+        OutputFormat::Csv => report_csv(&results),
+    }
+}
+
+/// Prints the human-readable text report.
+///
+/// Emojis and colors are disabled if stdout is not an interactive terminal
+/// (TTY), making it suitable for redirection to a file.
+fn report_text(results: Vec<FileResult>) {
     let total_files = results.len();
     let mut correct_files = 0;
     let mut incorrect_files = Vec::new();
     let mut error_files = Vec::new();
+    let mut fixed_files = Vec::new();
 
     let should_use_emojis = atty::is(atty::Stream::Stdout);
 
     for r in results {
         match r {
-            FileResult::Correct(_) => {
+            FileResult::Correct { .. } => {
                 correct_files += 1;
             }
             FileResult::Incorrect { path, declared_type, actual_type } => {
@@ -199,6 +578,10 @@ fn report(results: Vec<FileResult>) {
                 let emoji_prefix = if should_use_emojis { "⚠️ " } else { "" };
                 error_files.push(format!("{} {}: Error processing file - {}", emoji_prefix, path.display(), error_message.red()));
             }
+            FileResult::Fixed { path, new_path } => {
+                let emoji_prefix = if should_use_emojis { "🔧 " } else { "" };
+                fixed_files.push(format!("{} {} -> {}", emoji_prefix, path.display(), new_path.display().to_string().green()));
+            }
         }
     }
 
@@ -213,6 +596,10 @@ fn report(results: Vec<FileResult>) {
         println!("{}", format!("Incorrect: {}", incorrect_files.len()).red());
         println!("{}", format!("Errors: {}", error_files.len()).yellow());
     }
+    if !fixed_files.is_empty() {
+        let label = format!("Fixed: {}", fixed_files.len());
+        println!("{}", if should_use_emojis { format!("🔧 {}", label) } else { label }.green());
+    }
 
     if !incorrect_files.is_empty() {
         println!("\n--- Incorrect Files ---");
@@ -227,4 +614,103 @@ fn report(results: Vec<FileResult>) {
             println!("{}", r);
         }
     }
-}
\ No newline at end of file
+
+    if !fixed_files.is_empty() {
+        println!("\n--- Fixed Files ---");
+        for r in fixed_files {
+            println!("{}", r);
+        }
+    }
+}
+
+/// The top-level object emitted by the JSON report.
+#[derive(Serialize)]
+struct ReportSummary<'a> {
+    total: usize,
+    correct: usize,
+    incorrect: usize,
+    errors: usize,
+    fixed: usize,
+    results: &'a [FileResult],
+}
+
+/// Tallies the results into a `ReportSummary`.
+fn summarize(results: &[FileResult]) -> ReportSummary<'_> {
+    let mut summary = ReportSummary { total: results.len(), correct: 0, incorrect: 0, errors: 0, fixed: 0, results };
+    for r in results {
+        match r {
+            FileResult::Correct { .. } => summary.correct += 1,
+            FileResult::Incorrect { .. } => summary.incorrect += 1,
+            FileResult::Error { .. } => summary.errors += 1,
+            FileResult::Fixed { .. } => summary.fixed += 1,
+        }
+    }
+    summary
+}
+
+/// Prints the results as a single JSON summary object.
+fn report_json(results: &[FileResult]) {
+    let summary = summarize(results);
+    match serde_json::to_string_pretty(&summary) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("Failed to serialize report: {}", e),
+    }
+}
+
+/// Prints one CSV row per file: `path,status,declared_type,actual_type,error`.
+fn report_csv(results: &[FileResult]) {
+    println!("path,status,declared_type,actual_type,error");
+    for r in results {
+        let (path, status, declared, actual, error) = match r {
+            FileResult::Correct { path } => (path, "correct", String::new(), String::new(), String::new()),
+            FileResult::Incorrect { path, declared_type, actual_type } => {
+                (path, "incorrect", declared_type.clone(), actual_type.clone(), String::new())
+            }
+            FileResult::Error { path, error_message } => {
+                (path, "error", String::new(), String::new(), error_message.clone())
+            }
+            FileResult::Fixed { path, new_path } => {
+                (path, "fixed", String::new(), new_path.display().to_string(), String::new())
+            }
+        };
+        println!(
+            "{},{},{},{},{}",
+            csv_field(&path.display().to_string()),
+            status,
+            csv_field(&declared),
+            csv_field(&actual),
+            csv_field(&error),
+        );
+    }
+}
+
+/// Escapes a CSV field, quoting it when it contains a comma, quote, or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_base_strips_only_the_final_extension() {
+        assert_eq!(file_base(Path::new("report.v2.pdf")), "report.v2");
+        assert_eq!(file_base(Path::new("my.final.report.docx")), "my.final.report");
+        assert_eq!(file_base(Path::new("photo.jpg")), "photo");
+    }
+
+    #[test]
+    fn file_base_strips_known_compound_extensions() {
+        assert_eq!(file_base(Path::new("archive.tar.gz")), "archive");
+        assert_eq!(file_base(Path::new("a.b.c.tar.gz")), "a.b.c");
+    }
+
+    #[test]
+    fn file_base_preserves_hidden_files_without_extension() {
+        assert_eq!(file_base(Path::new(".bashrc")), ".bashrc");
+    }
+}
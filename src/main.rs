@@ -1,6 +1,6 @@
-use sigil::{run, AppConfig};
+use sigil::{run, AppConfig, OutputFormat};
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
 /// Sigil checks the integrity by comparing the file type to the type infered by the Magic Number of it
 #[derive(Parser, Debug)]
@@ -16,6 +16,52 @@ struct Cli {
     /// Recursively check all folders inside of that path
     #[arg(short, long)]
     recursive: bool,
+
+    /// Rename files whose extension contradicts their detected type
+    #[arg(long)]
+    fix: bool,
+
+    /// Show the renames that --fix would perform without touching disk
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Only verify files matching this glob (repeatable)
+    #[arg(long)]
+    include: Vec<String>,
+
+    /// Skip files and directories matching this glob (repeatable)
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// Output format for the report
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Descend into ZIP/TAR/GZIP containers and verify each entry
+    #[arg(long)]
+    into_archives: bool,
+
+    /// Maximum number of entries inspected per archive
+    #[arg(long, default_value_t = 10_000)]
+    max_archive_entries: usize,
+}
+
+/// Report output formats selectable on the command line.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl From<Format> for OutputFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Text => OutputFormat::Text,
+            Format::Json => OutputFormat::Json,
+            Format::Csv => OutputFormat::Csv,
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -25,14 +71,21 @@ fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         path: cli.path,
         input_json_file: cli.input_json_file,
         recursive: cli.recursive,
+        fix: cli.fix,
+        dry_run: cli.dry_run,
+        include: cli.include,
+        exclude: cli.exclude,
+        format: cli.format.into(),
+        into_archives: cli.into_archives,
+        max_archive_entries: cli.max_archive_entries,
     };
 
     if atty::isnt(atty::Stream::Stdout) {
         colored::control::set_override(false);
     }
 
-    println!("The path is: '{}'", config.path.display());
-    println!(
+    eprintln!("The path is: '{}'", config.path.display());
+    eprintln!(
         "The input file path is: '{}'",
         config.input_json_file.display()
     );
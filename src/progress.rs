@@ -0,0 +1,70 @@
+//! # Progress
+//!
+//! This module provides a lightweight progress-reporting subsystem for long
+//! recursive scans. A background reporter thread consumes `ProgressData`
+//! snapshots from a channel and prints the current stage and how many files
+//! have been checked so far, giving users feedback without altering the final
+//! report.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+
+/// A snapshot of the verification progress.
+///
+/// The scan is modeled as two stages: path discovery (stage 1) and file
+/// verification (stage 2). `entries_to_check` becomes known only once
+/// discovery completes, so it is zero while stage 1 is running.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgressData {
+    /// The stage currently running (1-based).
+    pub current_stage: u8,
+    /// The total number of stages.
+    pub max_stage: u8,
+    /// How many files have been verified so far.
+    pub entries_checked: usize,
+    /// How many files the current stage has to verify.
+    pub entries_to_check: usize,
+}
+
+/// Spawns the reporter thread.
+///
+/// The thread prints a carriage-return updated line to stderr roughly every
+/// 100 ms, reading the live count from the shared `entries_checked` counter and
+/// the stage information from the most recent `ProgressData` received on
+/// `receiver`. Output is suppressed when stderr is not an interactive terminal.
+/// The thread exits when the channel is closed.
+pub fn spawn_reporter(
+    receiver: Receiver<ProgressData>,
+    entries_checked: Arc<AtomicUsize>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let is_tty = atty::is(atty::Stream::Stderr);
+        let mut latest: Option<ProgressData> = None;
+
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(100)) {
+                Ok(data) => latest = Some(data),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if is_tty {
+                if let Some(data) = latest {
+                    let checked = entries_checked.load(Ordering::Relaxed);
+                    eprint!(
+                        "\rstage {}/{}: checked {} / {} files",
+                        data.current_stage, data.max_stage, checked, data.entries_to_check
+                    );
+                }
+            }
+        }
+
+        if is_tty && latest.is_some() {
+            eprintln!();
+        }
+    })
+}
@@ -16,6 +16,26 @@ struct SignatureEntry {
     pub r#type: String,
     pub offset: u32,
     pub signature: Vec<u8>,
+    /// Preferred extension for this type. When omitted, the lowercased type
+    /// label is used instead.
+    #[serde(default)]
+    pub extension: Option<String>,
+}
+
+/// The shape accepted by the signatures file.
+///
+/// A bare array of signature entries is supported for backwards compatibility;
+/// the structured form additionally carries an optional `aliases` table mapping
+/// each canonical type to the set of acceptable extensions.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SignatureDocument {
+    Structured {
+        signatures: Vec<SignatureEntry>,
+        #[serde(default)]
+        aliases: HashMap<String, Vec<String>>,
+    },
+    Bare(Vec<SignatureEntry>),
 }
 
 #[derive(Default, Debug)]
@@ -37,6 +57,14 @@ pub struct MagicNumberTrie {
     max_offset_len: u32,
     max_signature_len: u32,
     possible_offsets: Vec<u32>,
+    /// Maps each known file type to a preferred extension, used to repair
+    /// files whose extension contradicts their magic number.
+    pub extensions: HashMap<String, String>,
+    /// Maps each canonical file type to the set of extensions that are accepted
+    /// as declaring it (e.g. `JPEG -> {JPG, JPEG, JPE, JFIF}`). Extensions are
+    /// stored uppercased to match the declared types produced by the file
+    /// manager.
+    aliases: HashMap<String, HashSet<String>>,
 }
 
 impl MagicNumberTrie {
@@ -56,12 +84,29 @@ impl MagicNumberTrie {
     /// * The file content cannot be parsed as JSON.
     pub fn from_file(path: &Path) -> Result<Self, Box<dyn Error + Send + Sync>> {
         let content = read_to_string(path)?;
-        let signatures: Vec<SignatureEntry> = serde_json::from_str(&content)?;
+        let document: SignatureDocument = serde_json::from_str(&content)?;
+        let (signatures, aliases) = match document {
+            SignatureDocument::Structured { signatures, aliases } => (signatures, aliases),
+            SignatureDocument::Bare(signatures) => (signatures, HashMap::new()),
+        };
 
         let mut trie = MagicNumberTrie::default();
         let mut unique_offsets: HashSet<u32> = HashSet::new();
 
+        for (canonical_type, extensions) in aliases {
+            let set = extensions.into_iter().map(|e| e.to_uppercase()).collect();
+            trie.aliases.insert(canonical_type, set);
+        }
+
         for entry in signatures {
+            let preferred_extension = entry
+                .extension
+                .clone()
+                .unwrap_or_else(|| entry.r#type.to_lowercase());
+            trie.extensions
+                .entry(entry.r#type.clone())
+                .or_insert(preferred_extension);
+
             trie.insert(&entry.signature, entry.r#type);
 
             unique_offsets.insert(entry.offset);
@@ -119,6 +164,23 @@ impl MagicNumberTrie {
         None
     }
 
+    /// Reports whether a declared extension is acceptable for a detected type.
+    ///
+    /// When an alias set is configured for `actual_type`, the `declared_type`
+    /// must be a member of it. Otherwise the check falls back to the historical
+    /// substring comparison so types without an explicit alias table still work.
+    ///
+    /// # Arguments
+    ///
+    /// * `actual_type` - The canonical type detected from the magic number.
+    /// * `declared_type` - The uppercased extension declared by the file name.
+    pub fn accepts(&self, actual_type: &str, declared_type: &str) -> bool {
+        match self.aliases.get(actual_type) {
+            Some(extensions) => extensions.contains(declared_type),
+            None => actual_type.contains(declared_type),
+        }
+    }
+
     /// Performs a match operation within the Trie.
     fn trie_match(&self, file_header: &[u8]) -> Option<String> {
         let mut curr_node = &self.root;